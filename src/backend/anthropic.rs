@@ -0,0 +1,119 @@
+use std::env;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::message::Content;
+use crate::rate_limit::OutboundRateLimiter;
+
+use super::{ChatBackend, GenParams};
+
+const DEFAULT_MODEL: &str = "claude-sonnet-4-5";
+const API_URL: &str = "https://api.anthropic.com/v1/messages";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+// Anthropic requires `max_tokens`; Gemini/OpenAI/Ollama treat it as optional,
+// so fall back to a sane default when the caller didn't set one.
+const DEFAULT_MAX_TOKENS: usize = 1024;
+
+#[derive(Serialize)]
+struct AnthropicRequest {
+    model: String,
+    messages: Vec<AnthropicMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    max_tokens: usize,
+    temperature: f32,
+    top_p: f32,
+}
+
+#[derive(Serialize)]
+struct AnthropicMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct AnthropicResponseBody {
+    content: Vec<AnthropicContentBlock>,
+}
+
+#[derive(Deserialize)]
+struct AnthropicContentBlock {
+    text: String,
+}
+
+/// Talks to Anthropic's `/v1/messages` endpoint.
+pub struct AnthropicBackend {
+    api_key: String,
+    model: String,
+    outbound_limiter: OutboundRateLimiter,
+}
+
+impl AnthropicBackend {
+    pub fn from_env(outbound_limiter: OutboundRateLimiter) -> Self {
+        Self {
+            api_key: env::var("ANTHROPIC_API_KEY").expect("ANTHROPIC_API_KEY must be set when BACKEND=anthropic"),
+            model: env::var("ANTHROPIC_MODEL").unwrap_or_else(|_| DEFAULT_MODEL.to_string()),
+            outbound_limiter,
+        }
+    }
+}
+
+/// Anthropic's Messages API takes `system` as a top-level field rather than
+/// a message in the list, and has no "system" role among `messages`. See
+/// [`crate::message::to_generic_messages`] for the role mapping.
+fn to_anthropic_messages(messages: &[Content]) -> Vec<AnthropicMessage> {
+    crate::message::to_generic_messages(messages)
+        .into_iter()
+        .map(|(role, content)| AnthropicMessage { role, content })
+        .collect()
+}
+
+#[async_trait]
+impl ChatBackend for AnthropicBackend {
+    async fn generate(&self, messages: &[Content], params: &GenParams) -> Result<String, String> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+        let request_body = AnthropicRequest {
+            model: self.model.clone(),
+            messages: to_anthropic_messages(messages),
+            system: params.system_instruction.clone(),
+            max_tokens: params.max_output_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+            temperature: params.temperature,
+            top_p: params.top_p,
+        };
+
+        self.outbound_limiter.acquire().await;
+
+        let response = client
+            .post(API_URL)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request to Anthropic: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_else(|_| "unknown error".to_string());
+            return Err(format!("Anthropic returned HTTP {}: {}", status, body));
+        }
+
+        let parsed: AnthropicResponseBody = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Anthropic response: {}", e))?;
+
+        parsed
+            .content
+            .into_iter()
+            .next()
+            .map(|block| block.text)
+            .ok_or_else(|| "Anthropic response contained no content blocks".to_string())
+    }
+}
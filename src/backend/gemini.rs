@@ -0,0 +1,332 @@
+use std::env;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::message::{Content, Part};
+use crate::rate_limit::OutboundRateLimiter;
+
+use super::{ChatBackend, GenParams};
+
+const MODELS: [&str; 4] = [
+    "gemini-2.5-flash",
+    "gemini-flash-latest",
+    "gemini-pro-latest",
+    "gemini-2.0-flash",
+];
+const API_VERSIONS: [&str; 2] = ["v1beta", "v1"];
+
+#[derive(Serialize)]
+pub(crate) struct GeminiRequest {
+    contents: Vec<Content>,
+    #[serde(rename = "generationConfig")]
+    generation_config: GenerationConfig,
+    #[serde(rename = "systemInstruction", skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<Content>,
+}
+
+#[derive(Serialize)]
+struct GenerationConfig {
+    temperature: f32,
+    #[serde(rename = "topP")]
+    top_p: f32,
+    #[serde(rename = "maxOutputTokens", skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<usize>,
+}
+
+/// Builds the Gemini-shaped request body from the common [`GenParams`].
+/// Shared with the Vertex AI backend, which speaks the same wire format.
+pub(crate) fn build_request_body(messages: &[Content], params: &GenParams) -> GeminiRequest {
+    GeminiRequest {
+        contents: messages.to_vec(),
+        generation_config: GenerationConfig {
+            temperature: params.temperature,
+            top_p: params.top_p,
+            max_output_tokens: params.max_output_tokens,
+        },
+        system_instruction: params.system_instruction.as_ref().map(|text| Content {
+            role: "system".to_string(),
+            parts: vec![Part { text: text.clone() }],
+        }),
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct GeminiResponse {
+    pub candidates: Vec<Candidate>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct Candidate {
+    pub content: Content,
+}
+
+#[derive(Deserialize)]
+struct GeminiErrorResponse {
+    error: GeminiError,
+}
+
+#[derive(Deserialize)]
+struct GeminiError {
+    code: u16,
+    message: String,
+    status: String,
+}
+
+/// Talks to the public `generativelanguage.googleapis.com` Gemini API,
+/// falling back across a list of models/API versions until one answers.
+pub struct GeminiBackend {
+    outbound_limiter: OutboundRateLimiter,
+}
+
+impl GeminiBackend {
+    pub fn from_env(outbound_limiter: OutboundRateLimiter) -> Self {
+        Self { outbound_limiter }
+    }
+
+    /// Calls `:streamGenerateContent` and forwards each incremental text
+    /// delta to `tx` as it arrives. Tries the same model/version fallback
+    /// list as [`ChatBackend::generate`] before giving up.
+    pub async fn generate_stream(
+        &self,
+        messages: &[Content],
+        params: &GenParams,
+        tx: mpsc::Sender<Result<String, String>>,
+    ) {
+        let api_key = match env::var("GEMINI_API_KEY") {
+            Ok(key) => key,
+            Err(_) => {
+                let _ = tx
+                    .send(Err("GEMINI_API_KEY not found in environment variables".to_string()))
+                    .await;
+                return;
+            }
+        };
+
+        let client = match reqwest::Client::builder().timeout(Duration::from_secs(30)).build() {
+            Ok(client) => client,
+            Err(e) => {
+                let _ = tx.send(Err(format!("Failed to create HTTP client: {}", e))).await;
+                return;
+            }
+        };
+
+        let request_body = build_request_body(messages, params);
+
+        for api_version in &API_VERSIONS {
+            for model in &MODELS {
+                let url = format!(
+                    "https://generativelanguage.googleapis.com/{}/models/{}:streamGenerateContent?alt=sse&key={}",
+                    api_version, model, api_key
+                );
+
+                // Throttle fallback attempts too, so retrying across
+                // models/versions can't spike outbound traffic.
+                self.outbound_limiter.acquire().await;
+
+                let response = match client.post(&url).json(&request_body).send().await {
+                    Ok(resp) if resp.status().is_success() => {
+                        eprintln!("Streaming with {} / {}", api_version, model);
+                        resp
+                    }
+                    Ok(resp) => {
+                        eprintln!("HTTP {} from {} / {}", resp.status(), api_version, model);
+                        continue;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to send request to {}: {}", url, e);
+                        continue;
+                    }
+                };
+
+                let mut stream = response.bytes_stream();
+                // Raw bytes not yet decoded into `buffer`, so a multi-byte
+                // UTF-8 character split across a network chunk boundary
+                // gets carried over and combined instead of being lossily
+                // replaced (which would corrupt the text we then parse and
+                // persist into session history).
+                let mut byte_buffer: Vec<u8> = Vec::new();
+                let mut buffer = String::new();
+                let mut got_any = false;
+
+                while let Some(chunk) = stream.next().await {
+                    let chunk = match chunk {
+                        Ok(bytes) => bytes,
+                        Err(e) => {
+                            eprintln!("Error reading stream from {} / {}: {}", api_version, model, e);
+                            break;
+                        }
+                    };
+
+                    byte_buffer.extend_from_slice(&chunk);
+                    buffer.push_str(&crate::utf8_chunk::drain_valid_utf8(&mut byte_buffer));
+
+                    for object in drain_complete_json_objects(&mut buffer) {
+                        match serde_json::from_str::<GeminiResponse>(&object) {
+                            Ok(parsed) => {
+                                if let Some(text) = parsed
+                                    .candidates
+                                    .first()
+                                    .and_then(|c| c.content.parts.first())
+                                    .map(|p| p.text.clone())
+                                {
+                                    got_any = true;
+                                    if tx.send(Ok(text)).await.is_err() {
+                                        // Receiver dropped (client disconnected).
+                                        return;
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                eprintln!("Failed to parse stream chunk from {} / {}: {}", api_version, model, e);
+                            }
+                        }
+                    }
+                }
+
+                if got_any {
+                    return;
+                }
+                // No usable output from this model/version; fall through and try the next one.
+            }
+        }
+
+        let _ = tx
+            .send(Err(
+                "Failed to get response from Gemini API. Please check your API key and model availability.".to_string(),
+            ))
+            .await;
+    }
+}
+
+#[async_trait]
+impl ChatBackend for GeminiBackend {
+    async fn generate(&self, messages: &[Content], params: &GenParams) -> Result<String, String> {
+        let api_key = env::var("GEMINI_API_KEY")
+            .map_err(|_| "GEMINI_API_KEY not found in environment variables".to_string())?;
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+        let request_body = build_request_body(messages, params);
+
+        for api_version in &API_VERSIONS {
+            for model in &MODELS {
+                let url = format!(
+                    "https://generativelanguage.googleapis.com/{}/models/{}:generateContent?key={}",
+                    api_version, model, api_key
+                );
+
+                // Throttle fallback attempts too, so retrying across
+                // models/versions can't spike outbound traffic.
+                self.outbound_limiter.acquire().await;
+
+                let response = match client.post(&url).json(&request_body).send().await {
+                    Ok(resp) => {
+                        eprintln!("Trying: {} (model: {})", api_version, model);
+                        resp
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to send request to {}: {}", url, e);
+                        continue;
+                    }
+                };
+
+                let status = response.status();
+                let status_code = status.as_u16();
+
+                if status.is_success() {
+                    match response.json::<GeminiResponse>().await {
+                        Ok(gemini_response) => {
+                            if let Some(text) = gemini_response
+                                .candidates
+                                .first()
+                                .and_then(|c| c.content.parts.first())
+                                .map(|p| p.text.clone())
+                            {
+                                eprintln!("Success with {} / {}", api_version, model);
+                                return Ok(text);
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to parse response from {}: {}", url, e);
+                            continue;
+                        }
+                    }
+                } else {
+                    // Read response text first (can only consume once)
+                    let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+
+                    // Try to parse as structured error
+                    if let Ok(error_response) = serde_json::from_str::<GeminiErrorResponse>(&error_text) {
+                        eprintln!(
+                            "API error from {} / {}: {} ({}): {}",
+                            api_version, model, error_response.error.status, error_response.error.code, error_response.error.message
+                        );
+                    } else {
+                        eprintln!("HTTP {} from {} / {}: {}", status_code, api_version, model, error_text);
+                    }
+                    // Continue to next model/version
+                    continue;
+                }
+            }
+        }
+
+        Err("Failed to get response from Gemini API. Please check your API key and model availability.".to_string())
+    }
+}
+
+/// Scans `buffer` for complete top-level JSON objects (as emitted by
+/// Gemini's `:streamGenerateContent?alt=sse`, one object per `data:` line;
+/// text outside the objects, such as the `data:` prefixes, is skipped) and
+/// returns them, leaving any trailing partial object in `buffer` for the
+/// next chunk.
+fn drain_complete_json_objects(buffer: &mut String) -> Vec<String> {
+    let mut objects = Vec::new();
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = None;
+    let mut consumed_to = 0;
+
+    for (i, ch) in buffer.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match ch {
+            '"' => in_string = true,
+            '{' => {
+                if depth == 0 {
+                    start = Some(i);
+                }
+                depth += 1;
+            }
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    if let Some(s) = start.take() {
+                        objects.push(buffer[s..=i].to_string());
+                        consumed_to = i + ch.len_utf8();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    buffer.drain(0..consumed_to);
+    objects
+}
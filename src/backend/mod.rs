@@ -0,0 +1,86 @@
+mod anthropic;
+mod gemini;
+mod ollama;
+mod openai;
+mod vertex;
+
+pub use anthropic::AnthropicBackend;
+pub use gemini::GeminiBackend;
+pub use ollama::OllamaBackend;
+pub use openai::OpenAiBackend;
+pub use vertex::VertexAiBackend;
+
+use std::env;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::message::Content;
+use crate::rate_limit::OutboundRateLimiter;
+
+/// Sampling/tuning parameters common to every backend; each implementor
+/// maps these onto its own request shape.
+pub struct GenParams {
+    pub temperature: f32,
+    pub top_p: f32,
+    pub max_output_tokens: Option<usize>,
+    pub system_instruction: Option<String>,
+}
+
+/// A pluggable LLM provider. The Axum layer only ever talks to this trait,
+/// so swapping providers never touches `chat_handler` itself.
+#[async_trait]
+pub trait ChatBackend: Send + Sync {
+    async fn generate(&self, messages: &[Content], params: &GenParams) -> Result<String, String>;
+}
+
+/// The LLM providers selectable via the `BACKEND` env var.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidModel {
+    Gemini,
+    VertexAi,
+    OpenAi,
+    Ollama,
+    Anthropic,
+}
+
+impl FromStr for ValidModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "gemini" => Ok(Self::Gemini),
+            "vertexai" => Ok(Self::VertexAi),
+            "openai" => Ok(Self::OpenAi),
+            "ollama" => Ok(Self::Ollama),
+            "anthropic" => Ok(Self::Anthropic),
+            other => Err(format!(
+                "Unknown BACKEND '{}'; expected one of gemini, vertexai, openai, ollama, anthropic",
+                other
+            )),
+        }
+    }
+}
+
+impl ValidModel {
+    /// Reads `BACKEND` (default `gemini`).
+    pub fn from_env() -> Self {
+        env::var("BACKEND")
+            .unwrap_or_else(|_| "gemini".to_string())
+            .parse()
+            .expect("Invalid BACKEND value")
+    }
+}
+
+/// Constructs the [`ChatBackend`] selected by `model`, reading whatever
+/// provider-specific env vars it needs.
+pub fn build_backend(model: ValidModel, outbound_limiter: OutboundRateLimiter) -> Arc<dyn ChatBackend> {
+    match model {
+        ValidModel::Gemini => Arc::new(GeminiBackend::from_env(outbound_limiter)),
+        ValidModel::VertexAi => Arc::new(VertexAiBackend::from_env(outbound_limiter)),
+        ValidModel::OpenAi => Arc::new(OpenAiBackend::from_env(outbound_limiter)),
+        ValidModel::Ollama => Arc::new(OllamaBackend::from_env(outbound_limiter)),
+        ValidModel::Anthropic => Arc::new(AnthropicBackend::from_env(outbound_limiter)),
+    }
+}
@@ -0,0 +1,127 @@
+use std::env;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::message::Content;
+use crate::rate_limit::OutboundRateLimiter;
+
+use super::{ChatBackend, GenParams};
+
+const DEFAULT_MODEL: &str = "llama3";
+const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+
+#[derive(Serialize)]
+struct OllamaRequest {
+    model: String,
+    messages: Vec<OllamaMessage>,
+    stream: bool,
+    options: OllamaOptions,
+}
+
+#[derive(Serialize)]
+struct OllamaOptions {
+    temperature: f32,
+    top_p: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct OllamaMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct OllamaResponseBody {
+    message: OllamaMessageOut,
+}
+
+#[derive(Deserialize)]
+struct OllamaMessageOut {
+    content: String,
+}
+
+/// Talks to a local (or self-hosted) Ollama server's `/api/chat` endpoint.
+pub struct OllamaBackend {
+    base_url: String,
+    model: String,
+    outbound_limiter: OutboundRateLimiter,
+}
+
+impl OllamaBackend {
+    pub fn from_env(outbound_limiter: OutboundRateLimiter) -> Self {
+        Self {
+            base_url: env::var("OLLAMA_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string()),
+            model: env::var("OLLAMA_MODEL").unwrap_or_else(|_| DEFAULT_MODEL.to_string()),
+            outbound_limiter,
+        }
+    }
+}
+
+/// See [`crate::message::to_generic_messages`] for the role mapping.
+fn to_ollama_messages(messages: &[Content], system_instruction: &Option<String>) -> Vec<OllamaMessage> {
+    let mut out = Vec::with_capacity(messages.len() + 1);
+
+    if let Some(system) = system_instruction {
+        out.push(OllamaMessage {
+            role: "system".to_string(),
+            content: system.clone(),
+        });
+    }
+
+    out.extend(
+        crate::message::to_generic_messages(messages)
+            .into_iter()
+            .map(|(role, content)| OllamaMessage { role, content }),
+    );
+
+    out
+}
+
+#[async_trait]
+impl ChatBackend for OllamaBackend {
+    async fn generate(&self, messages: &[Content], params: &GenParams) -> Result<String, String> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(60))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+        let request_body = OllamaRequest {
+            model: self.model.clone(),
+            messages: to_ollama_messages(messages, &params.system_instruction),
+            stream: false,
+            options: OllamaOptions {
+                temperature: params.temperature,
+                top_p: params.top_p,
+                num_predict: params.max_output_tokens,
+            },
+        };
+
+        let url = format!("{}/api/chat", self.base_url);
+
+        self.outbound_limiter.acquire().await;
+
+        let response = client
+            .post(&url)
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Ollama at {}: {}", url, e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_else(|_| "unknown error".to_string());
+            return Err(format!("Ollama returned HTTP {}: {}", status, body));
+        }
+
+        let parsed: OllamaResponseBody = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Ollama response: {}", e))?;
+
+        Ok(parsed.message.content)
+    }
+}
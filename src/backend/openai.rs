@@ -0,0 +1,127 @@
+use std::env;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::message::Content;
+use crate::rate_limit::OutboundRateLimiter;
+
+use super::{ChatBackend, GenParams};
+
+const DEFAULT_MODEL: &str = "gpt-4o-mini";
+const API_URL: &str = "https://api.openai.com/v1/chat/completions";
+
+#[derive(Serialize)]
+struct OpenAiRequest {
+    model: String,
+    messages: Vec<OpenAiMessage>,
+    temperature: f32,
+    top_p: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct OpenAiMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct OpenAiResponseBody {
+    choices: Vec<OpenAiChoice>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiChoice {
+    message: OpenAiMessageOut,
+}
+
+#[derive(Deserialize)]
+struct OpenAiMessageOut {
+    content: String,
+}
+
+/// Talks to OpenAI's `/v1/chat/completions` endpoint.
+pub struct OpenAiBackend {
+    api_key: String,
+    model: String,
+    outbound_limiter: OutboundRateLimiter,
+}
+
+impl OpenAiBackend {
+    pub fn from_env(outbound_limiter: OutboundRateLimiter) -> Self {
+        Self {
+            api_key: env::var("OPENAI_API_KEY").expect("OPENAI_API_KEY must be set when BACKEND=openai"),
+            model: env::var("OPENAI_MODEL").unwrap_or_else(|_| DEFAULT_MODEL.to_string()),
+            outbound_limiter,
+        }
+    }
+}
+
+/// See [`crate::message::to_generic_messages`] for the role mapping.
+fn to_openai_messages(messages: &[Content], system_instruction: &Option<String>) -> Vec<OpenAiMessage> {
+    let mut out = Vec::with_capacity(messages.len() + 1);
+
+    if let Some(system) = system_instruction {
+        out.push(OpenAiMessage {
+            role: "system".to_string(),
+            content: system.clone(),
+        });
+    }
+
+    out.extend(
+        crate::message::to_generic_messages(messages)
+            .into_iter()
+            .map(|(role, content)| OpenAiMessage { role, content }),
+    );
+
+    out
+}
+
+#[async_trait]
+impl ChatBackend for OpenAiBackend {
+    async fn generate(&self, messages: &[Content], params: &GenParams) -> Result<String, String> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+        let request_body = OpenAiRequest {
+            model: self.model.clone(),
+            messages: to_openai_messages(messages, &params.system_instruction),
+            temperature: params.temperature,
+            top_p: params.top_p,
+            max_tokens: params.max_output_tokens,
+        };
+
+        self.outbound_limiter.acquire().await;
+
+        let response = client
+            .post(API_URL)
+            .bearer_auth(&self.api_key)
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request to OpenAI: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_else(|_| "unknown error".to_string());
+            return Err(format!("OpenAI returned HTTP {}: {}", status, body));
+        }
+
+        let parsed: OpenAiResponseBody = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse OpenAI response: {}", e))?;
+
+        parsed
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message.content)
+            .ok_or_else(|| "OpenAI response contained no choices".to_string())
+    }
+}
@@ -0,0 +1,93 @@
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::message::Content;
+use crate::rate_limit::OutboundRateLimiter;
+use crate::vertex_auth::AccessTokenProvider;
+
+use super::gemini::{build_request_body, GeminiResponse};
+use super::{ChatBackend, GenParams};
+
+const DEFAULT_MODEL: &str = "gemini-2.5-flash";
+
+/// Talks to Vertex AI's `:generateContent` endpoint, which speaks the same
+/// request/response shape as the public Gemini API but authenticates with
+/// an OAuth bearer token instead of an API key.
+pub struct VertexAiBackend {
+    project_id: String,
+    location: String,
+    model: String,
+    token_provider: Arc<AccessTokenProvider>,
+    outbound_limiter: OutboundRateLimiter,
+}
+
+impl VertexAiBackend {
+    pub fn from_env(outbound_limiter: OutboundRateLimiter) -> Self {
+        let project_id = env::var("PROJECT_ID").expect("PROJECT_ID must be set when BACKEND=vertexai");
+        let location = env::var("LOCATION").unwrap_or_else(|_| "us-central1".to_string());
+        let model = env::var("VERTEX_MODEL").unwrap_or_else(|_| DEFAULT_MODEL.to_string());
+        let credentials_path = env::var("GOOGLE_APPLICATION_CREDENTIALS")
+            .expect("GOOGLE_APPLICATION_CREDENTIALS must be set when BACKEND=vertexai");
+
+        let token_provider = AccessTokenProvider::from_adc_file(&credentials_path)
+            .expect("Failed to load Vertex AI service account credentials");
+
+        Self {
+            project_id,
+            location,
+            model,
+            token_provider: Arc::new(token_provider),
+            outbound_limiter,
+        }
+    }
+}
+
+#[async_trait]
+impl ChatBackend for VertexAiBackend {
+    async fn generate(&self, messages: &[Content], params: &GenParams) -> Result<String, String> {
+        let access_token = self.token_provider.access_token().await?;
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+        let url = format!(
+            "https://{}-aiplatform.googleapis.com/v1/projects/{}/locations/{}/publishers/google/models/{}:generateContent",
+            self.location, self.project_id, self.location, self.model
+        );
+
+        let request_body = build_request_body(messages, params);
+
+        self.outbound_limiter.acquire().await;
+
+        let response = client
+            .post(&url)
+            .bearer_auth(&access_token)
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request to Vertex AI: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_else(|_| "unknown error".to_string());
+            return Err(format!("Vertex AI returned HTTP {}: {}", status, body));
+        }
+
+        let parsed: GeminiResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse Vertex AI response: {}", e))?;
+
+        parsed
+            .candidates
+            .first()
+            .and_then(|c| c.content.parts.first())
+            .map(|p| p.text.clone())
+            .ok_or_else(|| "Vertex AI response contained no candidates".to_string())
+    }
+}
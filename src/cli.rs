@@ -1,9 +1,16 @@
+use futures_util::StreamExt;
 use std::io::{self, Write};
 
 pub async fn run_interactive_chat() {
     println!("🤖 Rust AI Chatbot - Interactive Mode");
     println!("Type 'exit' or 'quit' to end the conversation\n");
 
+    // Generated once per run so the server keeps this conversation's
+    // history across turns; sent back on every request to `/chat/stream`.
+    // Random rather than timestamp-derived, since this id is the only thing
+    // gating access to the conversation it names.
+    let session_id = format!("cli-{}", uuid::Uuid::new_v4());
+
     loop {
         print!("You: ");
         io::stdout().flush().unwrap();
@@ -22,14 +29,14 @@ pub async fn run_interactive_chat() {
                     break;
                 }
 
-                // Send request to local server
-                match send_chat_request(message).await {
-                    Ok(response) => {
-                        println!("Bot: {}\n", response);
-                    }
-                    Err(e) => {
-                        eprintln!("Error: {}\n", e);
-                    }
+                print!("Bot: ");
+                io::stdout().flush().unwrap();
+
+                // Stream tokens to stdout as they arrive instead of blocking
+                // on the full reply.
+                match stream_chat_request(message, &session_id).await {
+                    Ok(()) => println!("\n"),
+                    Err(e) => eprintln!("\nError: {}\n", e),
                 }
             }
             Err(error) => {
@@ -40,14 +47,17 @@ pub async fn run_interactive_chat() {
     }
 }
 
-async fn send_chat_request(message: &str) -> Result<String, String> {
+/// Posts to `/chat/stream` and prints each SSE `data:` delta to stdout as it
+/// arrives, flushing after every chunk so the reply feels live.
+async fn stream_chat_request(message: &str, session_id: &str) -> Result<(), String> {
     let client = reqwest::Client::new();
     let body = serde_json::json!({
-        "message": message
+        "message": message,
+        "session_id": session_id,
     });
 
     let response = client
-        .post("http://localhost:3000/chat")
+        .post("http://localhost:3000/chat/stream")
         .json(&body)
         .send()
         .await
@@ -58,13 +68,64 @@ async fn send_chat_request(message: &str) -> Result<String, String> {
         return Err(format!("Server error: {}", error_text));
     }
 
-    let chat_response: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    let mut stream = response.bytes_stream();
+    // Raw bytes not yet decoded into `buffer`, so a multi-byte UTF-8
+    // character split across a network chunk boundary gets carried over
+    // and combined instead of being lossily replaced.
+    let mut byte_buffer: Vec<u8> = Vec::new();
+    let mut buffer = String::new();
+    let mut pending_event = "message".to_string();
+    // An SSE event can be split across several `data:` lines (axum's
+    // `Event::data` emits one per `\n` in the original text); these must be
+    // rejoined with `\n` so embedded line breaks in the reply survive.
+    let mut pending_data_lines: Vec<String> = Vec::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Error reading stream: {}", e))?;
+        byte_buffer.extend_from_slice(&chunk);
+        buffer.push_str(&crate::utf8_chunk::drain_valid_utf8(&mut byte_buffer));
+
+        while let Some(pos) = buffer.find('\n') {
+            let line = buffer[..pos].trim_end_matches('\r').to_string();
+            buffer.drain(0..=pos);
+
+            if let Some(event) = line.strip_prefix("event:") {
+                pending_event = event.trim().to_string();
+                continue;
+            }
+
+            if let Some(data) = line.strip_prefix("data:") {
+                // The SSE spec strips at most one leading space (the
+                // mandatory field/value separator), not all whitespace —
+                // Gemini's deltas often start with a real space.
+                pending_data_lines.push(data.strip_prefix(' ').unwrap_or(data).to_string());
+                continue;
+            }
+
+            if !line.is_empty() {
+                continue;
+            }
+
+            // A blank line terminates the event; flush what we accumulated.
+            if pending_data_lines.is_empty() {
+                continue;
+            }
+            let data = pending_data_lines.join("\n");
+            pending_data_lines.clear();
+
+            if data == "[DONE]" {
+                return Ok(());
+            }
+
+            if pending_event == "error" {
+                return Err(data.to_string());
+            }
+
+            print!("{}", data);
+            io::stdout().flush().unwrap();
+            pending_event = "message".to_string();
+        }
+    }
 
-    chat_response["response"]
-        .as_str()
-        .map(|s| s.to_string())
-        .ok_or_else(|| "Invalid response format".to_string())
+    Ok(())
 }
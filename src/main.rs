@@ -1,24 +1,70 @@
+mod backend;
 mod cli;
+mod message;
+mod rate_limit;
+mod session;
+mod utf8_chunk;
+mod vertex_auth;
 
 use axum::{
-    extract::Json,
+    extract::{Json, Path, State},
     http::StatusCode,
-    response::Json as ResponseJson,
-    routing::{get, post},
+    middleware,
+    response::{
+        sse::{Event, Sse},
+        Json as ResponseJson,
+    },
+    routing::{delete, get, post},
     Router,
 };
+use futures_util::Stream;
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use std::env;
-use std::time::Duration;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use backend::{ChatBackend, GenParams, GeminiBackend, ValidModel};
+use message::{Content, Part};
+use rate_limit::{ClientRateLimiter, OutboundRateLimiter};
+use session::SessionStore;
+
+// Default outbound/inbound throughput cap when `MAX_REQUESTS_PER_SECOND`
+// isn't set; conservative enough to avoid tripping Gemini's free-tier quota.
+const DEFAULT_MAX_REQUESTS_PER_SECOND: f32 = 2.0;
+
+// Default sampling parameters applied when a request omits them.
+const DEFAULT_TEMPERATURE: f32 = 0.1;
+const DEFAULT_TOP_P: f32 = 0.95;
+
+/// Shared state handed to every Axum handler via the `State` extractor.
+#[derive(Clone)]
+struct AppState {
+    store: SessionStore,
+    outbound_limiter: OutboundRateLimiter,
+    backend: Arc<dyn ChatBackend>,
+    // Only populated when `BACKEND=gemini`: streaming is implemented as an
+    // inherent method on `GeminiBackend` rather than on the `ChatBackend`
+    // trait, since not every provider supports it yet.
+    gemini_stream_backend: Option<Arc<GeminiBackend>>,
+}
 
 #[derive(Deserialize)]
 struct ChatRequest {
     message: String,
+    session_id: Option<String>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    max_output_tokens: Option<usize>,
+    system_instruction: Option<String>,
 }
 
 #[derive(Serialize)]
 struct ChatResponse {
     response: String,
+    session_id: String,
 }
 
 #[derive(Serialize)]
@@ -26,137 +72,108 @@ struct ErrorResponse {
     error: String,
 }
 
-// Gemini API request structures
-#[derive(Serialize)]
-struct GeminiRequest {
-    contents: Vec<Content>,
-}
-
-#[derive(Serialize, Deserialize)]
-struct Content {
-    parts: Vec<Part>,
-}
-
-#[derive(Serialize, Deserialize)]
-struct Part {
-    text: String,
-}
-
-// Gemini API response structures
-#[derive(Deserialize)]
-struct GeminiResponse {
-    candidates: Vec<Candidate>,
+/// Builds the common [`GenParams`] every backend maps onto its own request
+/// shape, applying defaults for any tuning knob the caller omitted.
+fn gen_params_from_request(payload: &ChatRequest) -> GenParams {
+    GenParams {
+        temperature: payload.temperature.unwrap_or(DEFAULT_TEMPERATURE),
+        top_p: payload.top_p.unwrap_or(DEFAULT_TOP_P),
+        max_output_tokens: payload.max_output_tokens,
+        system_instruction: payload.system_instruction.clone(),
+    }
 }
 
-#[derive(Deserialize)]
-struct Candidate {
-    content: Content,
-}
+async fn chat_stream_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<ChatRequest>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, ResponseJson<ErrorResponse>)> {
+    if payload.message.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            ResponseJson(ErrorResponse {
+                error: "Message cannot be empty".to_string(),
+            }),
+        ));
+    }
 
-// Gemini API error response
-#[derive(Deserialize)]
-struct GeminiErrorResponse {
-    error: GeminiError,
-}
+    // Validate message length
+    if payload.message.len() > 10000 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            ResponseJson(ErrorResponse {
+                error: "Message is too long (max 10000 characters)".to_string(),
+            }),
+        ));
+    }
 
-#[derive(Deserialize)]
-struct GeminiError {
-    code: u16,
-    message: String,
-    status: String,
-}
+    let params = gen_params_from_request(&payload);
+    let session_id = payload.session_id.unwrap_or_else(uuid_like_id);
 
-async fn call_gemini_api(message: &str) -> Result<String, String> {
-    let api_key = env::var("GEMINI_API_KEY")
-        .map_err(|_| "GEMINI_API_KEY not found in environment variables".to_string())?;
-
-    // Try different models - Use actual available models from API
-    // Models that support generateContent: gemini-2.5-flash, gemini-flash-latest, gemini-pro-latest, etc.
-    let models = ["gemini-2.5-flash", "gemini-flash-latest", "gemini-pro-latest", "gemini-2.0-flash"];
-    let api_versions = ["v1beta", "v1"];
-
-    // Create HTTP client with timeout
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-    let request_body = GeminiRequest {
-        contents: vec![Content {
-            parts: vec![Part {
-                text: message.to_string(),
-            }],
+    let mut history = state.store.history(&session_id);
+    history.push(Content {
+        role: "user".to_string(),
+        parts: vec![Part {
+            text: payload.message.clone(),
         }],
-    };
+    });
+
+    let (deltas_tx, mut deltas_rx) = mpsc::channel::<Result<String, String>>(32);
+    match state.gemini_stream_backend.clone() {
+        Some(gemini) => {
+            let history_for_stream = history.clone();
+            tokio::spawn(async move {
+                gemini.generate_stream(&history_for_stream, &params, deltas_tx).await;
+            });
+        }
+        None => {
+            // The configured backend only implements the non-streaming `ChatBackend` trait so far.
+            tokio::spawn(async move {
+                let _ = deltas_tx
+                    .send(Err("Streaming is not yet supported on the configured backend; use /chat instead.".to_string()))
+                    .await;
+            });
+        }
+    }
+
+    let store = state.store.clone();
+    let (events_tx, events_rx) = mpsc::channel::<Event>(32);
+    tokio::spawn(async move {
+        let mut reply = String::new();
 
-    // Try different API versions and models
-    for api_version in &api_versions {
-        for model in &models {
-            let url = format!(
-                "https://generativelanguage.googleapis.com/{}/models/{}:generateContent?key={}",
-                api_version, model, api_key
-            );
-
-            let response = match client
-                .post(&url)
-                .json(&request_body)
-                .send()
-                .await
-            {
-                Ok(resp) => {
-                    eprintln!("Trying: {} (model: {})", api_version, model);
-                    resp
+        while let Some(chunk) = deltas_rx.recv().await {
+            let event = match chunk {
+                Ok(delta) => {
+                    reply.push_str(&delta);
+                    Event::default().data(delta)
                 }
                 Err(e) => {
-                    eprintln!("Failed to send request to {}: {}", url, e);
-                    continue;
+                    eprintln!("Error streaming from backend: {}", e);
+                    Event::default().event("error").data(e)
                 }
             };
-
-            let status = response.status();
-            let status_code = status.as_u16();
-
-            if status.is_success() {
-                match response.json::<GeminiResponse>().await {
-                    Ok(gemini_response) => {
-                        if let Some(text) = gemini_response
-                            .candidates
-                            .first()
-                            .and_then(|c| c.content.parts.first())
-                            .map(|p| p.text.clone())
-                        {
-                            eprintln!("Success with {} / {}", api_version, model);
-                            return Ok(text);
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to parse response from {}: {}", url, e);
-                        continue;
-                    }
-                }
-            } else {
-                // Read response text first (can only consume once)
-                let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-
-                // Try to parse as structured error
-                if let Ok(error_response) = serde_json::from_str::<GeminiErrorResponse>(&error_text) {
-                    eprintln!(
-                        "API error from {} / {}: {} ({}): {}",
-                        api_version, model, error_response.error.status, error_response.error.code, error_response.error.message
-                    );
-                } else {
-                    eprintln!("HTTP {} from {} / {}: {}", status_code, api_version, model, error_text);
-                }
-                // Continue to next model/version
-                continue;
+            if events_tx.send(event).await.is_err() {
+                return;
             }
         }
-    }
 
-    Err("Failed to get response from Gemini API. Please check your API key and model availability.".to_string())
+        if !reply.is_empty() {
+            history.push(Content {
+                role: "model".to_string(),
+                parts: vec![Part { text: reply }],
+            });
+            store.replace(&session_id, history);
+        }
+
+        let _ = events_tx.send(Event::default().data("[DONE]")).await;
+    });
+
+    Ok(Sse::new(ReceiverStream::new(events_rx).map(Ok)))
 }
 
-async fn chat_handler(Json(payload): Json<ChatRequest>) -> Result<ResponseJson<ChatResponse>, (StatusCode, ResponseJson<ErrorResponse>)> {
+async fn chat_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<ChatRequest>,
+) -> Result<ResponseJson<ChatResponse>, (StatusCode, ResponseJson<ErrorResponse>)> {
     if payload.message.trim().is_empty() {
         return Err((
             StatusCode::BAD_REQUEST,
@@ -176,10 +193,34 @@ async fn chat_handler(Json(payload): Json<ChatRequest>) -> Result<ResponseJson<C
         ));
     }
 
-    match call_gemini_api(&payload.message).await {
-        Ok(response) => Ok(ResponseJson(ChatResponse { response })),
+    let params = gen_params_from_request(&payload);
+    let session_id = payload.session_id.unwrap_or_else(uuid_like_id);
+
+    let mut history = state.store.history(&session_id);
+    history.push(Content {
+        role: "user".to_string(),
+        parts: vec![Part {
+            text: payload.message.clone(),
+        }],
+    });
+
+    match state.backend.generate(&history, &params).await {
+        Ok(response) => {
+            history.push(Content {
+                role: "model".to_string(),
+                parts: vec![Part {
+                    text: response.clone(),
+                }],
+            });
+            state.store.replace(&session_id, history);
+
+            Ok(ResponseJson(ChatResponse {
+                response,
+                session_id,
+            }))
+        }
         Err(e) => {
-            eprintln!("Error calling Gemini API: {}", e);
+            eprintln!("Error calling backend: {}", e);
             Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 ResponseJson(ErrorResponse { error: e }),
@@ -188,6 +229,22 @@ async fn chat_handler(Json(payload): Json<ChatRequest>) -> Result<ResponseJson<C
     }
 }
 
+async fn clear_session_handler(
+    State(state): State<AppState>,
+    Path(session_id): Path<String>,
+) -> StatusCode {
+    state.store.clear(&session_id);
+    StatusCode::NO_CONTENT
+}
+
+/// Generates an unguessable session id. Session ids are the only thing
+/// gating access to a conversation's history (`/chat`) and its deletion
+/// (`DELETE /chat/:session_id`), so this must be cryptographically random
+/// rather than derived from a timestamp.
+fn uuid_like_id() -> String {
+    format!("sess-{}", uuid::Uuid::new_v4())
+}
+
 #[tokio::main]
 async fn main() {
     // Load environment variables
@@ -200,8 +257,10 @@ async fn main() {
         return;
     }
 
+    let model = ValidModel::from_env();
+
     // Verify API key is set
-    if env::var("GEMINI_API_KEY").is_err() {
+    if model == ValidModel::Gemini && env::var("GEMINI_API_KEY").is_err() {
         eprintln!("Warning: GEMINI_API_KEY not found in environment variables");
         eprintln!("   Please create a .env file with your API key");
     }
@@ -211,22 +270,60 @@ async fn main() {
         "OK"
     }
 
-    // Build the application router
+    let max_requests_per_second: f32 = env::var("MAX_REQUESTS_PER_SECOND")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_REQUESTS_PER_SECOND);
+
+    let outbound_limiter = OutboundRateLimiter::new(max_requests_per_second);
+    let chat_backend = backend::build_backend(model, outbound_limiter.clone());
+
+    // Streaming only has a concrete implementation for Gemini so far; see
+    // `AppState::gemini_stream_backend`.
+    let gemini_stream_backend = if model == ValidModel::Gemini {
+        Some(Arc::new(GeminiBackend::from_env(outbound_limiter.clone())))
+    } else {
+        None
+    };
+
+    let state = AppState {
+        store: SessionStore::new(),
+        outbound_limiter,
+        backend: chat_backend,
+        gemini_stream_backend,
+    };
+    let client_limiter = ClientRateLimiter::new(max_requests_per_second);
+
+    // Rate limiting applies to all the chat routes (including the session
+    // DELETE route), not to the health check.
+    let chat_routes = Router::new()
+        .route("/chat", post(chat_handler))
+        .route("/chat/stream", post(chat_stream_handler))
+        .route("/chat/:session_id", delete(clear_session_handler))
+        .route_layer(middleware::from_fn_with_state(
+            client_limiter,
+            rate_limit::client_rate_limit,
+        ));
+
     let app = Router::new()
         .route("/", get(health))
         .route("/health", get(health))
-        .route("/chat", post(chat_handler));
+        .merge(chat_routes)
+        .with_state(state);
 
     // Run the server
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000")
         .await
         .expect("Failed to bind to port 3000. Is another server running?");
 
-    println!("üöÄ Server running on http://localhost:3000");
-    println!("üìù POST to http://localhost:3000/chat with {{ \"message\": \"your message\" }}");
-    println!("üí° Health check: http://localhost:3000/health");
+    println!("üöÄ Server running on http://localhost:3000");
+    println!("üìù POST to http://localhost:3000/chat with {{ \"message\": \"your message\" }}");
+    println!("üí° Health check: http://localhost:3000/health");
 
-    axum::serve(listener, app)
-        .await
-        .expect("Server failed to start");
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .expect("Server failed to start");
 }
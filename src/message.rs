@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// The common internal chat message shape every backend maps onto its own
+/// wire format: a speaking `role` ("user", "model"/"assistant", or
+/// "system") and the text parts of that turn.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Content {
+    pub role: String,
+    pub parts: Vec<Part>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Part {
+    pub text: String,
+}
+
+/// Maps our internal `role` ("user"/"model"/"system") onto the generic
+/// `user`/`assistant`/`system` roles used by OpenAI-compatible and
+/// Anthropic-style chat APIs, and flattens each turn's parts into a single
+/// string (those APIs carry plain text, not parts). Returns `(role, text)`
+/// pairs for the caller to map into its own wire message struct.
+pub fn to_generic_messages(messages: &[Content]) -> Vec<(String, String)> {
+    messages
+        .iter()
+        .map(|content| {
+            let role = match content.role.as_str() {
+                "model" => "assistant",
+                other => other,
+            };
+            let text = content.parts.iter().map(|p| p.text.as_str()).collect::<Vec<_>>().join("\n");
+            (role.to_string(), text)
+        })
+        .collect()
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use axum::extract::{ConnectInfo, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use std::net::SocketAddr;
+
+/// Caps how many distinct client IPs we track so a flood of one-off callers
+/// can't grow the limiter's memory without bound, mirroring the eviction
+/// policy used by [`crate::session::SessionStore`].
+const MAX_TRACKED_CLIENTS: usize = 1000;
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+// A non-positive configured rate (e.g. a `MAX_REQUESTS_PER_SECOND=0` typo
+// while trying to "disable" the limiter) would make `wait_time` divide by
+// zero or a negative number, producing a `Duration` that panics the
+// lock-holding thread and poisons the bucket's mutex for the rest of the
+// process. Clamp to a small positive floor instead of ever hitting that path.
+const MIN_REQUESTS_PER_SECOND: f32 = 0.1;
+
+/// A simple token bucket: `capacity` tokens refilled continuously at
+/// `refill_per_sec` tokens/second, never exceeding `capacity`.
+struct TokenBucket {
+    tokens: f64,
+    capacity: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(requests_per_second: f32) -> Self {
+        let refill_per_sec = requests_per_second.max(MIN_REQUESTS_PER_SECOND);
+        let capacity = refill_per_sec.max(1.0) as f64;
+        Self {
+            tokens: capacity,
+            capacity,
+            refill_per_sec: refill_per_sec as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Takes one token if available, returning whether it succeeded.
+    fn try_take(&mut self) -> bool {
+        self.refill();
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Seconds until the next token will be available, if none is free now.
+    fn wait_time(&mut self) -> Option<Duration> {
+        self.refill();
+        if self.tokens >= 1.0 {
+            None
+        } else {
+            let missing = 1.0 - self.tokens;
+            Some(Duration::from_secs_f64(missing / self.refill_per_sec))
+        }
+    }
+}
+
+/// Per-client-IP token-bucket rate limiter used as Axum middleware on the
+/// chat routes. Exhausted buckets reject the request with `429`.
+#[derive(Clone)]
+pub struct ClientRateLimiter {
+    requests_per_second: f32,
+    buckets: std::sync::Arc<Mutex<Inner>>,
+}
+
+#[derive(Default)]
+struct Inner {
+    order: Vec<IpAddr>,
+    buckets: HashMap<IpAddr, TokenBucket>,
+}
+
+impl ClientRateLimiter {
+    pub fn new(requests_per_second: f32) -> Self {
+        Self {
+            requests_per_second,
+            buckets: std::sync::Arc::new(Mutex::new(Inner::default())),
+        }
+    }
+
+    fn try_acquire(&self, ip: IpAddr) -> bool {
+        let mut inner = self.buckets.lock().unwrap();
+        if !inner.buckets.contains_key(&ip) {
+            inner.order.push(ip);
+            inner
+                .buckets
+                .insert(ip, TokenBucket::new(self.requests_per_second));
+            while inner.buckets.len() > MAX_TRACKED_CLIENTS {
+                let oldest = inner.order.remove(0);
+                inner.buckets.remove(&oldest);
+            }
+        }
+        inner.buckets.get_mut(&ip).unwrap().try_take()
+    }
+}
+
+/// Axum middleware that throttles each client IP to the configured
+/// `max_requests_per_second`, returning `429 TOO_MANY_REQUESTS` once a
+/// client's bucket is empty.
+pub async fn client_rate_limit(
+    State(limiter): State<ClientRateLimiter>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: axum::extract::Request,
+    next: Next,
+) -> Response {
+    if limiter.try_acquire(addr.ip()) {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            axum::Json(ErrorResponse {
+                error: "Rate limit exceeded. Please slow down your requests.".to_string(),
+            }),
+        )
+            .into_response()
+    }
+}
+
+/// A single, process-wide token bucket guarding outbound calls to the
+/// upstream LLM API, so the model/version fallback loop in
+/// [`crate::backend::GeminiBackend`] can't spike traffic when it retries.
+#[derive(Clone)]
+pub struct OutboundRateLimiter {
+    bucket: std::sync::Arc<Mutex<TokenBucket>>,
+}
+
+impl OutboundRateLimiter {
+    pub fn new(requests_per_second: f32) -> Self {
+        Self {
+            bucket: std::sync::Arc::new(Mutex::new(TokenBucket::new(requests_per_second))),
+        }
+    }
+
+    /// Waits, if necessary, until a token is available, then consumes it.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap();
+                if bucket.try_take() {
+                    return;
+                }
+                bucket.wait_time()
+            };
+            if let Some(duration) = wait {
+                tokio::time::sleep(duration).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::message::Content;
+
+/// Caps how many turns (user + model messages) we keep per session so a
+/// long-running conversation can't grow the in-memory store without bound.
+const MAX_HISTORY_LEN: usize = 40;
+
+/// Caps how many distinct sessions we hold at once. When exceeded, the
+/// oldest-inserted session is evicted to make room for the new one.
+const MAX_SESSIONS: usize = 1000;
+
+#[derive(Default)]
+struct Inner {
+    // Keeps insertion order so we can evict the oldest session first.
+    order: Vec<String>,
+    sessions: HashMap<String, Vec<Content>>,
+}
+
+/// Shared, in-memory store of per-session conversation history.
+///
+/// Cheap to clone: it's a handle around an `Arc<Mutex<_>>`, so it can be
+/// threaded through Axum's `State` extractor like any other shared resource.
+#[derive(Clone, Default)]
+pub struct SessionStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl SessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the stored history for `session_id`, or an empty history if
+    /// the session is new or was evicted.
+    pub fn history(&self, session_id: &str) -> Vec<Content> {
+        let inner = self.inner.lock().unwrap();
+        inner.sessions.get(session_id).cloned().unwrap_or_default()
+    }
+
+    /// Replaces the stored history for `session_id`, truncating to the
+    /// most recent `MAX_HISTORY_LEN` turns and evicting the oldest session
+    /// if the store has grown past `MAX_SESSIONS`.
+    pub fn replace(&self, session_id: &str, mut history: Vec<Content>) {
+        if history.len() > MAX_HISTORY_LEN {
+            let excess = history.len() - MAX_HISTORY_LEN;
+            history.drain(0..excess);
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        if !inner.sessions.contains_key(session_id) {
+            inner.order.push(session_id.to_string());
+        }
+        inner.sessions.insert(session_id.to_string(), history);
+
+        while inner.sessions.len() > MAX_SESSIONS {
+            let oldest = inner.order.remove(0);
+            inner.sessions.remove(&oldest);
+        }
+    }
+
+    /// Drops all history for `session_id`.
+    pub fn clear(&self, session_id: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.sessions.remove(session_id);
+        inner.order.retain(|id| id != session_id);
+    }
+}
@@ -0,0 +1,43 @@
+/// Decodes as much valid UTF-8 as possible out of `buffer`, draining those
+/// bytes and returning them as a `String`. Bytes left over because a
+/// multi-byte character was split across a network chunk boundary are kept
+/// in `buffer` for the caller to complete on the next call, instead of
+/// being lossily replaced with U+FFFD.
+///
+/// Shared by the CLI ([`crate::cli`]) and the Gemini streaming backend
+/// ([`crate::backend::gemini`]), which both decode incrementally arriving
+/// SSE byte chunks.
+pub fn drain_valid_utf8(buffer: &mut Vec<u8>) -> String {
+    let mut out = String::new();
+
+    loop {
+        match std::str::from_utf8(buffer) {
+            Ok(valid) => {
+                out.push_str(valid);
+                buffer.clear();
+                break;
+            }
+            Err(e) => {
+                let valid_up_to = e.valid_up_to();
+                out.push_str(std::str::from_utf8(&buffer[..valid_up_to]).unwrap());
+
+                match e.error_len() {
+                    // A genuinely invalid byte sequence (not just a chunk
+                    // boundary split): replace it and keep decoding the rest.
+                    Some(invalid_len) => {
+                        out.push('\u{FFFD}');
+                        buffer.drain(0..valid_up_to + invalid_len);
+                    }
+                    // An incomplete sequence trailing the buffer; keep it
+                    // for the next chunk and stop here.
+                    None => {
+                        buffer.drain(0..valid_up_to);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    out
+}
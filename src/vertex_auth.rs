@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const TOKEN_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+// Refresh this many seconds before the cached token's real expiry so a
+// request in flight never races a token that just went stale.
+const REFRESH_SKEW_SECS: u64 = 60;
+
+/// The subset of a GCP Application Default Credentials (service account)
+/// JSON file that we need to mint OAuth access tokens.
+#[derive(Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+#[derive(Serialize)]
+struct Claims {
+    iss: String,
+    scope: String,
+    aud: String,
+    iat: u64,
+    exp: u64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+struct CachedToken {
+    access_token: String,
+    expires_at_unix: u64,
+}
+
+/// Mints and caches short-lived OAuth access tokens for the Vertex AI
+/// backend by signing a JWT with a service account's private key and
+/// exchanging it at Google's OAuth token endpoint.
+pub struct AccessTokenProvider {
+    key: ServiceAccountKey,
+    cached: Mutex<Option<CachedToken>>,
+}
+
+impl AccessTokenProvider {
+    /// Loads a service account key from the Application Default Credentials
+    /// JSON file at `path` (the file an env var like
+    /// `GOOGLE_APPLICATION_CREDENTIALS` usually points to).
+    pub fn from_adc_file(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            format!("Failed to read GOOGLE_APPLICATION_CREDENTIALS file '{}': {}", path, e)
+        })?;
+        let key: ServiceAccountKey = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse service account key '{}': {}", path, e))?;
+
+        Ok(Self {
+            key,
+            cached: Mutex::new(None),
+        })
+    }
+
+    /// Returns a valid bearer token, minting a fresh one if none is cached
+    /// or the cached one is about to expire.
+    pub async fn access_token(&self) -> Result<String, String> {
+        let now = unix_now();
+
+        {
+            let guard = self.cached.lock().unwrap();
+            if let Some(cached) = guard.as_ref() {
+                if cached.expires_at_unix > now + REFRESH_SKEW_SECS {
+                    return Ok(cached.access_token.clone());
+                }
+            }
+        }
+
+        let token = self.mint_token(now).await?;
+        let access_token = token.access_token.clone();
+        *self.cached.lock().unwrap() = Some(token);
+        Ok(access_token)
+    }
+
+    async fn mint_token(&self, now: u64) -> Result<CachedToken, String> {
+        let claims = Claims {
+            iss: self.key.client_email.clone(),
+            scope: TOKEN_SCOPE.to_string(),
+            aud: self.key.token_uri.clone(),
+            iat: now,
+            exp: now + 3600,
+        };
+
+        let header = jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256);
+        let encoding_key = jsonwebtoken::EncodingKey::from_rsa_pem(self.key.private_key.as_bytes())
+            .map_err(|e| format!("Invalid service account private key: {}", e))?;
+        let assertion = jsonwebtoken::encode(&header, &claims, &encoding_key)
+            .map_err(|e| format!("Failed to sign JWT: {}", e))?;
+
+        let client = reqwest::Client::new();
+        let response = client
+            .post(&self.key.token_uri)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", assertion.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach OAuth token endpoint: {}", e))?;
+
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_else(|_| "unknown error".to_string());
+            return Err(format!("OAuth token exchange failed: {}", body));
+        }
+
+        let parsed: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse OAuth token response: {}", e))?;
+
+        Ok(CachedToken {
+            access_token: parsed.access_token,
+            expires_at_unix: now + parsed.expires_in,
+        })
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}